@@ -1,12 +1,11 @@
-use anyhow::Result;
-
 #[allow(dead_code)]
 #[derive(Debug,PartialEq, Eq)]
-enum Token {
-    Illegal,
+pub(crate) enum Token<'a> {
     Eof,
-    Ident(String),
+    Ident(&'a str),
     Int(String),
+    Float(String),
+    Str(String),
     Assign,
     Comma,
     Semicolon,
@@ -36,26 +35,89 @@ enum Token {
     Plus,
 }
 
-struct Lexer {
+/// A source location, spanning `[start, end)` byte offsets, with the
+/// 1-based `line`/`column` of the span's first byte.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Span {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+/// Errors produced while lexing, each carrying the [`Span`] of the
+/// offending input so callers can point diagnostics at it.
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LexError {
+    UnexpectedChar { ch: char, span: Span },
+    UnterminatedString { span: Span },
+    UnterminatedComment { span: Span },
+    InvalidNumber { literal: String, span: Span },
+}
+
+impl LexError {
+    pub(crate) fn span(&self) -> Span {
+        match self {
+            LexError::UnexpectedChar { span, .. } => *span,
+            LexError::UnterminatedString { span } => *span,
+            LexError::UnterminatedComment { span } => *span,
+            LexError::InvalidNumber { span, .. } => *span,
+        }
+    }
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LexError::UnexpectedChar { ch, span } => {
+                write!(f, "unexpected character '{}' at {}:{}", ch, span.line, span.column)
+            },
+            LexError::UnterminatedString { span } => {
+                write!(f, "unterminated string literal starting at {}:{}", span.line, span.column)
+            },
+            LexError::UnterminatedComment { span } => {
+                write!(f, "unterminated block comment starting at {}:{}", span.line, span.column)
+            },
+            LexError::InvalidNumber { literal, span } => {
+                write!(f, "invalid number literal '{}' at {}:{}", literal, span.line, span.column)
+            },
+        }
+    }
+}
+
+impl std::error::Error for LexError {}
+
+pub(crate) struct Lexer<'a> {
     position: usize,
     read_position: usize,
     ch: u8,
-    input: Vec<u8>,
+    input: &'a [u8],
+    line: usize,
+    column: usize,
 }
 
-impl Lexer {
-    fn new(input: String) -> Lexer {
+impl<'a> Lexer<'a> {
+    pub(crate) fn new(input: &'a str) -> Lexer<'a> {
         let mut lex = Lexer {
             position: 0,
             read_position: 0,
             ch: 0,
-            input: input.into_bytes(),
+            input: input.as_bytes(),
+            line: 1,
+            column: 0,
         };
         lex.read_char();
         return lex;
     }
 
     fn read_char(&mut self) {
+        if self.ch == b'\n' {
+            self.line += 1;
+            self.column = 0;
+        }
+        self.column += 1;
         if self.read_position >= self.input.len() {
             self.ch = 0;
         } else {
@@ -65,8 +127,11 @@ impl Lexer {
         self.read_position += 1;
     }
 
-    fn next_token(&mut self) -> Result<Token> {
-        self.skip_whitespace();
+    pub(crate) fn next_token(&mut self) -> Result<(Token<'a>, Span), LexError> {
+        self.skip_whitespace()?;
+        let start = self.position;
+        let start_line = self.line;
+        let start_column = self.column;
         let token = match self.ch {
             b'{' => Token::Lsquirly,
             b'}' => Token::Rsquirly,
@@ -91,7 +156,7 @@ impl Lexer {
                 } else {
                     Token::Bang
                 }
-            } 
+            }
             b'/' => Token::Slash,
             b'*' => Token::Asterisk,
             b'<' => {
@@ -112,7 +177,7 @@ impl Lexer {
             },
             b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
                 let ident = self.read_identifier();
-                return Ok(match ident.as_str() {
+                let token = match ident {
                     "fn" => Token::Function,
                     "let" => Token::Let,
                     "true" => Token::True,
@@ -121,17 +186,33 @@ impl Lexer {
                     "else" => Token::Else,
                     "return" => Token::Return,
                     _ => Token::Ident(ident),
-                });
+                };
+                let span = Span { line: start_line, column: start_column, start, end: self.position };
+                return Ok((token, span));
             },
             b'0'..=b'9' => {
-                let number = self.read_number();
-                return Ok(Token::Int(number));
+                let token = self.read_number(start, start_line, start_column)?;
+                let span = Span { line: start_line, column: start_column, start, end: self.position };
+                return Ok((token, span));
+            },
+            b'"' => {
+                let string = self.read_string(start, start_line, start_column)?;
+                let span = Span { line: start_line, column: start_column, start, end: self.position };
+                return Ok((Token::Str(string), span));
+            },
+            0 => {
+                let span = Span { line: start_line, column: start_column, start, end: start };
+                return Ok((Token::Eof, span));
+            },
+            _ => {
+                let ch = self.read_utf8_char();
+                let span = Span { line: start_line, column: start_column, start, end: self.position };
+                return Err(LexError::UnexpectedChar { ch, span });
             },
-            0 => Token::Eof,
-            _ => Token::Illegal,
         };
         self.read_char();
-        return Ok(token);
+        let span = Span { line: start_line, column: start_column, start, end: self.position };
+        return Ok((token, span));
     }
 
     fn peek_char(&self) -> u8 {
@@ -142,38 +223,178 @@ impl Lexer {
         }
     }
 
-    fn skip_whitespace(&mut self) {
-        while self.ch.is_ascii_whitespace() {
-            self.read_char();
+    /// As in C, a bare `/*` always starts a block comment, even where two
+    /// adjacent operators (`/` followed by `*`) were intended instead.
+    fn skip_whitespace(&mut self) -> Result<(), LexError> {
+        loop {
+            if self.ch.is_ascii_whitespace() {
+                self.read_char();
+            } else if self.ch == b'/' && self.peek_char() == b'/' {
+                while self.ch != b'\n' && self.ch != 0 {
+                    self.read_char();
+                }
+            } else if self.ch == b'/' && self.peek_char() == b'*' {
+                let start = self.position;
+                let start_line = self.line;
+                let start_column = self.column;
+                self.read_char();
+                self.read_char();
+                while !(self.ch == b'*' && self.peek_char() == b'/') && self.ch != 0 {
+                    self.read_char();
+                }
+                if self.ch != 0 {
+                    self.read_char();
+                    self.read_char();
+                } else {
+                    let span = Span { line: start_line, column: start_column, start, end: self.position };
+                    return Err(LexError::UnterminatedComment { span });
+                }
+            } else {
+                break;
+            }
         }
+        return Ok(());
     }
 
-    fn read_identifier(&mut self) -> String {
+    fn read_identifier(&mut self) -> &'a str {
         let position = self.position;
-        while self.ch.is_ascii_alphabetic() {
+        while self.ch.is_ascii_alphanumeric() || self.ch == b'_' {
             self.read_char();
         }
-        return String::from_utf8(self.input[position..self.position].to_vec()).unwrap();
+        return std::str::from_utf8(&self.input[position..self.position]).unwrap();
     }
 
-    fn read_number(&mut self) -> String {
+    /// Reads an integer or floating-point literal, allowing `_` digit
+    /// separators. A single `.` followed by a digit switches the literal to
+    /// a `Token::Float`; a second `.` is a malformed-number error.
+    fn read_number(&mut self, start: usize, start_line: usize, start_column: usize) -> Result<Token<'a>, LexError> {
         let position = self.position;
-        while self.ch.is_ascii_digit() {
+        let mut is_float = false;
+        loop {
+            match self.ch {
+                b'0'..=b'9' | b'_' => self.read_char(),
+                b'.' if !is_float && self.peek_char().is_ascii_digit() => {
+                    is_float = true;
+                    self.read_char();
+                },
+                b'.' if is_float => {
+                    let literal = std::str::from_utf8(&self.input[position..self.position]).unwrap().to_string();
+                    self.read_char();
+                    let span = Span { line: start_line, column: start_column, start, end: self.position };
+                    return Err(LexError::InvalidNumber { literal, span });
+                },
+                _ => break,
+            }
+        }
+        let literal: String = std::str::from_utf8(&self.input[position..self.position])
+            .unwrap()
+            .chars()
+            .filter(|&ch| ch != '_')
+            .collect();
+        if is_float {
+            Ok(Token::Float(literal))
+        } else {
+            Ok(Token::Int(literal))
+        }
+    }
+
+    /// Reads a double-quoted string literal, starting with `self.ch == b'"'`,
+    /// processing `\n`, `\t`, `\"`, `\\` and `\0` escapes. Leaves `self.ch`
+    /// positioned just past the closing quote.
+    fn read_string(&mut self, start: usize, start_line: usize, start_column: usize) -> Result<String, LexError> {
+        let mut string = String::new();
+        self.read_char();
+        loop {
+            match self.ch {
+                0 => {
+                    let span = Span { line: start_line, column: start_column, start, end: self.position };
+                    return Err(LexError::UnterminatedString { span });
+                },
+                b'"' => {
+                    self.read_char();
+                    return Ok(string);
+                },
+                b'\\' => {
+                    self.read_char();
+                    if self.ch == 0 {
+                        continue;
+                    }
+                    match self.read_utf8_char() {
+                        'n' => string.push('\n'),
+                        't' => string.push('\t'),
+                        '"' => string.push('"'),
+                        '\\' => string.push('\\'),
+                        '0' => string.push('\0'),
+                        other => string.push(other),
+                    }
+                },
+                _ => {
+                    string.push(self.read_utf8_char());
+                },
+            }
+        }
+    }
+
+    /// Decodes the full UTF-8 scalar value starting at `self.ch` (the
+    /// source is a valid `&str`, so multi-byte characters must be read as a
+    /// unit rather than cast byte-by-byte), advancing past all of its bytes.
+    fn read_utf8_char(&mut self) -> char {
+        let start = self.position;
+        let width = match self.ch {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            _ => 4,
+        };
+        for _ in 0..width {
             self.read_char();
         }
-        return String::from_utf8(self.input[position..self.position].to_vec()).unwrap();
+        std::str::from_utf8(&self.input[start..self.position]).unwrap().chars().next().unwrap()
     }
 }
-    
+
+/// Yields tokens up to (but not including) `Eof`, surfacing lex errors as
+/// `Some(Err(_))` so callers can tell a malformed byte apart from a clean
+/// end of input. Callers that need spans should drive `next_token` directly
+/// (see [`lex`]).
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token<'a>, LexError>;
+
+    fn next(&mut self) -> Option<Result<Token<'a>, LexError>> {
+        match self.next_token() {
+            Ok((Token::Eof, _)) => None,
+            Ok((token, _)) => Some(Ok(token)),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Drives a [`Lexer`] over `input` to completion, collecting every token
+/// (including the trailing `Eof`) along with its [`Span`].
+#[allow(dead_code)]
+pub(crate) fn lex(input: &str) -> Result<Vec<(Token<'_>, Span)>, LexError> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let (token, span) = lexer.next_token()?;
+        let is_eof = token == Token::Eof;
+        tokens.push((token, span));
+        if is_eof {
+            break;
+        }
+    }
+    return Ok(tokens);
+}
+
 
 #[cfg(test)]
 mod tests {
-    use anyhow::Result;
-    use super::{Token, Lexer};
+    use super::{Token, Lexer, LexError, lex};
+
     #[test]
-    fn test_next_token() -> Result<()>{
+    fn test_next_token() -> Result<(), LexError> {
         let input = "=+(){},;";
-        let mut lexer = Lexer::new(input.into());
+        let mut lexer = Lexer::new(input);
         let tokens = vec![
             Token::Assign,
             Token::Plus,
@@ -186,7 +407,7 @@ mod tests {
         ];
 
         for token in tokens {
-            let next_token = lexer.next_token()?;
+            let (next_token, _span) = lexer.next_token()?;
             assert_eq!(token, next_token);
         }
 
@@ -194,7 +415,7 @@ mod tests {
     }
 
     #[test]
-    fn test_next_token_two() -> Result<()>{
+    fn test_next_token_two() -> Result<(), LexError> {
         let input = r#"
             let five = 5;
             let ten = 10;
@@ -206,49 +427,49 @@ mod tests {
             let result = add(five, ten);
         "#;
 
-        let mut lexer = Lexer::new(input.into());
+        let mut lexer = Lexer::new(input);
         let tokens = vec![
             Token::Let,
-            Token::Ident("five".into()),
+            Token::Ident("five"),
             Token::Assign,
             Token::Int("5".into()),
             Token::Semicolon,
             Token::Let,
-            Token::Ident("ten".into()),
+            Token::Ident("ten"),
             Token::Assign,
             Token::Int("10".into()),
             Token::Semicolon,
             Token::Let,
-            Token::Ident("add".into()),
+            Token::Ident("add"),
             Token::Assign,
             Token::Function,
             Token::Lparen,
-            Token::Ident("x".into()),
+            Token::Ident("x"),
             Token::Comma,
-            Token::Ident("y".into()),
+            Token::Ident("y"),
             Token::Rparen,
             Token::Lsquirly,
-            Token::Ident("x".into()),
+            Token::Ident("x"),
             Token::Plus,
-            Token::Ident("y".into()),
+            Token::Ident("y"),
             Token::Semicolon,
             Token::Rsquirly,
             Token::Semicolon,
             Token::Let,
-            Token::Ident("result".into()),
+            Token::Ident("result"),
             Token::Assign,
-            Token::Ident("add".into()),
+            Token::Ident("add"),
             Token::Lparen,
-            Token::Ident("five".into()),
+            Token::Ident("five"),
             Token::Comma,
-            Token::Ident("ten".into()),
+            Token::Ident("ten"),
             Token::Rparen,
             Token::Semicolon,
-            Token::Eof,       
+            Token::Eof,
         ];
 
         for token in tokens {
-            let next_token = lexer.next_token()?;
+            let (next_token, _span) = lexer.next_token()?;
             assert_eq!(token, next_token);
         }
 
@@ -256,7 +477,17 @@ mod tests {
     }
 
     #[test]
-    fn test_next_token_three() -> Result<()>{
+    fn test_identifier_with_underscore_and_digits() -> Result<(), LexError> {
+        let mut lexer = Lexer::new("let foo_bar2 = 1;");
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Let);
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Ident("foo_bar2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_token_three() -> Result<(), LexError> {
         let input = r#"
             let five = 5;
             let ten = 10;
@@ -266,7 +497,7 @@ mod tests {
             };
 
             let result = add(five, ten);
-            !-/*5;
+            !-/ *5; // space keeps `/ *` two operators instead of a block comment
             5 < 10 > 5;
             5 == 10;
             5 != 10;
@@ -274,42 +505,42 @@ mod tests {
             5 <= 10;
         "#;
 
-        let mut lexer = Lexer::new(input.into());
+        let mut lexer = Lexer::new(input);
         let tokens = vec![
             Token::Let,
-            Token::Ident("five".into()),
-            Token::Assign, 
+            Token::Ident("five"),
+            Token::Assign,
             Token::Int("5".into()),
             Token::Semicolon,
             Token::Let,
-            Token::Ident("ten".into()),
+            Token::Ident("ten"),
             Token::Assign,
             Token::Int("10".into()),
             Token::Semicolon,
             Token::Let,
-            Token::Ident("add".into()),
+            Token::Ident("add"),
             Token::Assign,
             Token::Function,
             Token::Lparen,
-            Token::Ident("x".into()),
+            Token::Ident("x"),
             Token::Comma,
-            Token::Ident("y".into()),
+            Token::Ident("y"),
             Token::Rparen,
             Token::Lsquirly,
-            Token::Ident("x".into()),
+            Token::Ident("x"),
             Token::Plus,
-            Token::Ident("y".into()),
+            Token::Ident("y"),
             Token::Semicolon,
             Token::Rsquirly,
             Token::Semicolon,
             Token::Let,
-            Token::Ident("result".into()),
+            Token::Ident("result"),
             Token::Assign,
-            Token::Ident("add".into()),
+            Token::Ident("add"),
             Token::Lparen,
-            Token::Ident("five".into()),
+            Token::Ident("five"),
             Token::Comma,
-            Token::Ident("ten".into()),
+            Token::Ident("ten"),
             Token::Rparen,
             Token::Semicolon,
             Token::Bang,
@@ -344,11 +575,180 @@ mod tests {
         ];
 
         for token in tokens {
-            let next_token = lexer.next_token()?;
+            let (next_token, _span) = lexer.next_token()?;
             assert_eq!(token, next_token);
         }
 
         Ok(())
     }
 
+    #[test]
+    fn test_next_token_spans() -> Result<(), LexError> {
+        let input = "let x =\n  5;";
+        let mut lexer = Lexer::new(input);
+
+        let (token, span) = lexer.next_token()?;
+        assert_eq!(token, Token::Let);
+        assert_eq!((span.line, span.column, span.start, span.end), (1, 1, 0, 3));
+
+        let (token, span) = lexer.next_token()?;
+        assert_eq!(token, Token::Ident("x"));
+        assert_eq!((span.line, span.column, span.start, span.end), (1, 5, 4, 5));
+
+        let (token, span) = lexer.next_token()?;
+        assert_eq!(token, Token::Assign);
+        assert_eq!((span.line, span.column, span.start, span.end), (1, 7, 6, 7));
+
+        let (token, span) = lexer.next_token()?;
+        assert_eq!(token, Token::Int("5".into()));
+        assert_eq!((span.line, span.column, span.start, span.end), (2, 3, 10, 11));
+
+        let (token, span) = lexer.next_token()?;
+        assert_eq!(token, Token::Semicolon);
+        assert_eq!((span.line, span.column, span.start, span.end), (2, 4, 11, 12));
+
+        let (token, span) = lexer.next_token()?;
+        assert_eq!(token, Token::Eof);
+        assert_eq!(span.start, span.end);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_literal_empty() -> Result<(), LexError> {
+        let mut lexer = Lexer::new(r#""""#);
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Str("".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_literal_escapes() -> Result<(), LexError> {
+        let mut lexer = Lexer::new(r#""a\n\t\"\\\0b""#);
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Str("a\n\t\"\\\0b".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_literal_utf8() -> Result<(), LexError> {
+        let mut lexer = Lexer::new(r#""héllo""#);
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Str("héllo".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_literal_unterminated_is_error() {
+        let mut lexer = Lexer::new(r#""abc"#);
+        assert!(matches!(lexer.next_token(), Err(LexError::UnterminatedString { .. })));
+    }
+
+    #[test]
+    fn test_line_comment_is_skipped() -> Result<(), LexError> {
+        let mut lexer = Lexer::new("// a comment\nlet x = 5;");
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Let);
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_comment_is_skipped() -> Result<(), LexError> {
+        let mut lexer = Lexer::new("/* a\nmulti-line comment */let x = 5;");
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Let);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unterminated_block_comment_is_error() {
+        let mut lexer = Lexer::new("/* a comment that never ends");
+        assert!(matches!(lexer.next_token(), Err(LexError::UnterminatedComment { .. })));
+    }
+
+    #[test]
+    fn test_slash_is_still_an_operator() -> Result<(), LexError> {
+        let mut lexer = Lexer::new("5 / 2");
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Int("5".into()));
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Slash);
+        Ok(())
+    }
+
+    #[test]
+    fn test_float_literal() -> Result<(), LexError> {
+        let mut lexer = Lexer::new("3.14;");
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Float("3.14".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_number_with_underscore_separators() -> Result<(), LexError> {
+        let mut lexer = Lexer::new("1_000_000;");
+        let (token, _span) = lexer.next_token()?;
+        assert_eq!(token, Token::Int("1000000".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_number_with_two_decimal_points_is_error() {
+        let mut lexer = Lexer::new("1.2.3");
+        assert!(matches!(lexer.next_token(), Err(LexError::InvalidNumber { .. })));
+    }
+
+    #[test]
+    fn test_unexpected_char_is_error() {
+        let mut lexer = Lexer::new("@");
+        assert!(matches!(lexer.next_token(), Err(LexError::UnexpectedChar { ch: '@', .. })));
+    }
+
+    #[test]
+    fn test_unexpected_char_decodes_full_utf8_scalar() {
+        let mut lexer = Lexer::new("é");
+        assert!(matches!(lexer.next_token(), Err(LexError::UnexpectedChar { ch: 'é', .. })));
+    }
+
+    #[test]
+    fn test_lex_free_function() -> Result<(), LexError> {
+        let tokens = lex("let x = 5;")?;
+        let kinds: Vec<Token> = tokens.into_iter().map(|(token, _span)| token).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Token::Let,
+                Token::Ident("x"),
+                Token::Assign,
+                Token::Int("5".into()),
+                Token::Semicolon,
+                Token::Eof,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lexer_iterator_stops_before_eof() -> Result<(), LexError> {
+        let tokens: Vec<Token> = Lexer::new("let x = 5;").collect::<Result<_, _>>()?;
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Let,
+                Token::Ident("x"),
+                Token::Assign,
+                Token::Int("5".into()),
+                Token::Semicolon,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lexer_iterator_surfaces_errors() {
+        let results: Vec<Result<Token, LexError>> = Lexer::new("let @ = 5;").collect();
+        assert_eq!(results[0], Ok(Token::Let));
+        assert!(matches!(results[1], Err(LexError::UnexpectedChar { .. })));
+    }
+
 }
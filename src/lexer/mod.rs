@@ -0,0 +1,2 @@
+pub(crate) mod lexer;
+pub(crate) mod repl;
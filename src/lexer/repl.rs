@@ -7,14 +7,22 @@ impl Repl {
         loop {
             let mut line = String::new();
             _ = std::io::stdin().read_line(&mut line)?;
-            let mut lexer = Lexer::new(line.into());
+            let mut lexer = Lexer::new(&line);
 
             loop {
-                let token = lexer.next_token()?;
-                if token == Token::Eof {
-                    break;
+                match lexer.next_token() {
+                    Ok((token, span)) => {
+                        if token == Token::Eof {
+                            break;
+                        }
+                        println!("{:?} {}:{}", token, span.line, span.column);
+                    },
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        eprintln!("{}^", " ".repeat(err.span().column.saturating_sub(1)));
+                        break;
+                    },
                 }
-                println!("{:?}", token);
             }
         }
     }
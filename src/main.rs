@@ -0,0 +1,13 @@
+// Parts of this project (notably the lexer) use an explicit-`return` style, and the
+// lexer/parser submodule files are intentionally named after their parent
+// directories (`lexer::lexer`, `parser::parser`).
+#![allow(clippy::needless_return, clippy::module_inception)]
+
+mod lexer;
+mod parser;
+
+use lexer::repl::Repl;
+
+fn main() -> anyhow::Result<()> {
+    Repl::run()
+}
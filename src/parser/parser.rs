@@ -0,0 +1,435 @@
+use crate::lexer::lexer::{lex, LexError, Span, Token};
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Program<'a> {
+    statements: Vec<Statement<'a>>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Identifier<'a> {
+    name: &'a str,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct BlockStatement<'a> {
+    statements: Vec<Statement<'a>>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Statement<'a> {
+    Let { name: Identifier<'a>, value: Expression<'a> },
+    Return { return_value: Expression<'a> },
+    Expression { expression: Expression<'a> },
+}
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum PrefixOperator {
+    Bang,
+    Minus,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum InfixOperator {
+    Plus,
+    Minus,
+    Slash,
+    Asterisk,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Expression<'a> {
+    Identifier(&'a str),
+    IntegerLiteral(String),
+    FloatLiteral(String),
+    StringLiteral(String),
+    Boolean(bool),
+    Prefix { operator: PrefixOperator, right: Box<Expression<'a>> },
+    Infix { left: Box<Expression<'a>>, operator: InfixOperator, right: Box<Expression<'a>> },
+    FunctionLiteral { parameters: Vec<Identifier<'a>>, body: BlockStatement<'a> },
+}
+
+/// Parse errors, each carrying the [`Span`] of the offending token.
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ParseError {
+    UnexpectedToken { span: Span },
+    UnexpectedEof { span: Span },
+}
+
+#[allow(dead_code)]
+impl ParseError {
+    pub(crate) fn span(&self) -> Span {
+        match self {
+            ParseError::UnexpectedToken { span } => *span,
+            ParseError::UnexpectedEof { span } => *span,
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedToken { span } => {
+                write!(f, "unexpected token at {}:{}", span.line, span.column)
+            },
+            ParseError::UnexpectedEof { span } => {
+                write!(f, "unexpected end of input at {}:{}", span.line, span.column)
+            },
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Binding powers for infix operators, loosest to tightest. Each operator's
+// left/right pair controls associativity: equal powers (the common case)
+// are left-associative because the right power is one higher than the left.
+#[allow(dead_code)]
+const LOWEST: u8 = 0;
+#[allow(dead_code)]
+const PREFIX_BP: u8 = 11;
+
+#[allow(dead_code)]
+fn infix_operator(token: &Token) -> Option<InfixOperator> {
+    match token {
+        Token::Equal => Some(InfixOperator::Equal),
+        Token::NotEqual => Some(InfixOperator::NotEqual),
+        Token::LessThan => Some(InfixOperator::LessThan),
+        Token::LessThanOrEqual => Some(InfixOperator::LessThanOrEqual),
+        Token::GreaterThan => Some(InfixOperator::GreaterThan),
+        Token::GreaterThanOrEqual => Some(InfixOperator::GreaterThanOrEqual),
+        Token::Plus => Some(InfixOperator::Plus),
+        Token::Minus => Some(InfixOperator::Minus),
+        Token::Slash => Some(InfixOperator::Slash),
+        Token::Asterisk => Some(InfixOperator::Asterisk),
+        _ => None,
+    }
+}
+
+#[allow(dead_code)]
+fn binding_power(operator: &InfixOperator) -> (u8, u8) {
+    match operator {
+        InfixOperator::Equal | InfixOperator::NotEqual => (1, 2),
+        InfixOperator::LessThan
+        | InfixOperator::LessThanOrEqual
+        | InfixOperator::GreaterThan
+        | InfixOperator::GreaterThanOrEqual => (3, 4),
+        InfixOperator::Plus | InfixOperator::Minus => (5, 6),
+        InfixOperator::Slash | InfixOperator::Asterisk => (7, 8),
+    }
+}
+
+#[allow(dead_code)]
+pub(crate) struct Parser<'a> {
+    tokens: Vec<(Token<'a>, Span)>,
+    pos: usize,
+}
+
+#[allow(dead_code)]
+impl<'a> Parser<'a> {
+    pub(crate) fn new(input: &'a str) -> Result<Parser<'a>, LexError> {
+        Ok(Parser { tokens: lex(input)?, pos: 0 })
+    }
+
+    fn peek(&self) -> &Token<'a> {
+        &self.tokens[self.pos].0
+    }
+
+    fn peek_span(&self) -> Span {
+        self.tokens[self.pos].1
+    }
+
+    /// Takes ownership of the current token (leaving `Token::Eof` in its
+    /// place) and advances, unless already at the final (`Eof`) slot.
+    fn bump_token(&mut self) -> (Token<'a>, Span) {
+        let span = self.tokens[self.pos].1;
+        let token = std::mem::replace(&mut self.tokens[self.pos].0, Token::Eof);
+        if self.pos + 1 < self.tokens.len() {
+            self.pos += 1;
+        }
+        (token, span)
+    }
+
+    fn expect(&mut self, expected: Token<'a>) -> Result<(), ParseError> {
+        let span = self.peek_span();
+        if *self.peek() == expected {
+            self.bump_token();
+            Ok(())
+        } else {
+            Err(ParseError::UnexpectedToken { span })
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<&'a str, ParseError> {
+        let span = self.peek_span();
+        match self.peek() {
+            Token::Ident(_) => {
+                let Token::Ident(name) = self.bump_token().0 else { unreachable!() };
+                Ok(name)
+            },
+            _ => Err(ParseError::UnexpectedToken { span }),
+        }
+    }
+
+    pub(crate) fn parse_program(&mut self) -> Result<Program<'a>, ParseError> {
+        let mut statements = Vec::new();
+        while *self.peek() != Token::Eof {
+            statements.push(self.parse_statement()?);
+        }
+        Ok(Program { statements })
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement<'a>, ParseError> {
+        match self.peek() {
+            Token::Let => self.parse_let_statement(),
+            Token::Return => self.parse_return_statement(),
+            _ => self.parse_expression_statement(),
+        }
+    }
+
+    fn parse_let_statement(&mut self) -> Result<Statement<'a>, ParseError> {
+        self.bump_token();
+        let name = self.expect_ident()?;
+        self.expect(Token::Assign)?;
+        let value = self.parse_expression(LOWEST)?;
+        if matches!(self.peek(), Token::Semicolon) {
+            self.bump_token();
+        }
+        Ok(Statement::Let { name: Identifier { name }, value })
+    }
+
+    fn parse_return_statement(&mut self) -> Result<Statement<'a>, ParseError> {
+        self.bump_token();
+        let return_value = self.parse_expression(LOWEST)?;
+        if matches!(self.peek(), Token::Semicolon) {
+            self.bump_token();
+        }
+        Ok(Statement::Return { return_value })
+    }
+
+    fn parse_expression_statement(&mut self) -> Result<Statement<'a>, ParseError> {
+        let expression = self.parse_expression(LOWEST)?;
+        if matches!(self.peek(), Token::Semicolon) {
+            self.bump_token();
+        }
+        Ok(Statement::Expression { expression })
+    }
+
+    fn parse_block_statement(&mut self) -> Result<BlockStatement<'a>, ParseError> {
+        let mut statements = Vec::new();
+        while !matches!(self.peek(), Token::Rsquirly | Token::Eof) {
+            statements.push(self.parse_statement()?);
+        }
+        self.expect(Token::Rsquirly)?;
+        Ok(BlockStatement { statements })
+    }
+
+    fn parse_function_parameters(&mut self) -> Result<Vec<Identifier<'a>>, ParseError> {
+        let mut parameters = Vec::new();
+        if matches!(self.peek(), Token::Rparen) {
+            self.bump_token();
+            return Ok(parameters);
+        }
+        parameters.push(Identifier { name: self.expect_ident()? });
+        while matches!(self.peek(), Token::Comma) {
+            self.bump_token();
+            parameters.push(Identifier { name: self.expect_ident()? });
+        }
+        self.expect(Token::Rparen)?;
+        Ok(parameters)
+    }
+
+    /// Parses one prefix/atom expression, then folds in infix operators
+    /// whose left binding power is at least `min_bp`, recursing into the
+    /// right-hand side with that operator's right binding power.
+    fn parse_expression(&mut self, min_bp: u8) -> Result<Expression<'a>, ParseError> {
+        let mut left = self.parse_atom()?;
+        while let Some(operator) = infix_operator(self.peek()) {
+            let (left_bp, right_bp) = binding_power(&operator);
+            if left_bp < min_bp {
+                break;
+            }
+            self.bump_token();
+            let right = self.parse_expression(right_bp)?;
+            left = Expression::Infix { left: Box::new(left), operator, right: Box::new(right) };
+        }
+        Ok(left)
+    }
+
+    fn parse_atom(&mut self) -> Result<Expression<'a>, ParseError> {
+        let span = self.peek_span();
+        match self.peek() {
+            Token::Ident(_) => {
+                let Token::Ident(name) = self.bump_token().0 else { unreachable!() };
+                Ok(Expression::Identifier(name))
+            },
+            Token::Int(_) => {
+                let Token::Int(literal) = self.bump_token().0 else { unreachable!() };
+                Ok(Expression::IntegerLiteral(literal))
+            },
+            Token::Float(_) => {
+                let Token::Float(literal) = self.bump_token().0 else { unreachable!() };
+                Ok(Expression::FloatLiteral(literal))
+            },
+            Token::Str(_) => {
+                let Token::Str(literal) = self.bump_token().0 else { unreachable!() };
+                Ok(Expression::StringLiteral(literal))
+            },
+            Token::True => {
+                self.bump_token();
+                Ok(Expression::Boolean(true))
+            },
+            Token::False => {
+                self.bump_token();
+                Ok(Expression::Boolean(false))
+            },
+            Token::Bang => {
+                self.bump_token();
+                let right = self.parse_expression(PREFIX_BP)?;
+                Ok(Expression::Prefix { operator: PrefixOperator::Bang, right: Box::new(right) })
+            },
+            Token::Minus => {
+                self.bump_token();
+                let right = self.parse_expression(PREFIX_BP)?;
+                Ok(Expression::Prefix { operator: PrefixOperator::Minus, right: Box::new(right) })
+            },
+            Token::Lparen => {
+                self.bump_token();
+                let expression = self.parse_expression(LOWEST)?;
+                self.expect(Token::Rparen)?;
+                Ok(expression)
+            },
+            Token::Function => {
+                self.bump_token();
+                self.expect(Token::Lparen)?;
+                let parameters = self.parse_function_parameters()?;
+                self.expect(Token::Lsquirly)?;
+                let body = self.parse_block_statement()?;
+                Ok(Expression::FunctionLiteral { parameters, body })
+            },
+            Token::Eof => Err(ParseError::UnexpectedEof { span }),
+            _ => Err(ParseError::UnexpectedToken { span }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_let_statement() {
+        let mut parser = Parser::new("let x = 5;").unwrap();
+        let program = parser.parse_program().unwrap();
+        assert_eq!(
+            program,
+            Program {
+                statements: vec![Statement::Let {
+                    name: Identifier { name: "x" },
+                    value: Expression::IntegerLiteral("5".into()),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_return_statement() {
+        let mut parser = Parser::new("return 10;").unwrap();
+        let program = parser.parse_program().unwrap();
+        assert_eq!(
+            program,
+            Program {
+                statements: vec![Statement::Return { return_value: Expression::IntegerLiteral("10".into()) }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_infix_precedence() {
+        let mut parser = Parser::new("1 + 2 * 3;").unwrap();
+        let program = parser.parse_program().unwrap();
+        assert_eq!(
+            program,
+            Program {
+                statements: vec![Statement::Expression {
+                    expression: Expression::Infix {
+                        left: Box::new(Expression::IntegerLiteral("1".into())),
+                        operator: InfixOperator::Plus,
+                        right: Box::new(Expression::Infix {
+                            left: Box::new(Expression::IntegerLiteral("2".into())),
+                            operator: InfixOperator::Asterisk,
+                            right: Box::new(Expression::IntegerLiteral("3".into())),
+                        }),
+                    },
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_prefix_and_grouping() {
+        let mut parser = Parser::new("-(1 + 2);").unwrap();
+        let program = parser.parse_program().unwrap();
+        assert_eq!(
+            program,
+            Program {
+                statements: vec![Statement::Expression {
+                    expression: Expression::Prefix {
+                        operator: PrefixOperator::Minus,
+                        right: Box::new(Expression::Infix {
+                            left: Box::new(Expression::IntegerLiteral("1".into())),
+                            operator: InfixOperator::Plus,
+                            right: Box::new(Expression::IntegerLiteral("2".into())),
+                        }),
+                    },
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_let_function_literal() {
+        let mut parser = Parser::new("let add = fn(x, y) { x + y; };").unwrap();
+        let program = parser.parse_program().unwrap();
+        assert_eq!(
+            program,
+            Program {
+                statements: vec![Statement::Let {
+                    name: Identifier { name: "add" },
+                    value: Expression::FunctionLiteral {
+                        parameters: vec![Identifier { name: "x" }, Identifier { name: "y" }],
+                        body: BlockStatement {
+                            statements: vec![Statement::Expression {
+                                expression: Expression::Infix {
+                                    left: Box::new(Expression::Identifier("x")),
+                                    operator: InfixOperator::Plus,
+                                    right: Box::new(Expression::Identifier("y")),
+                                },
+                            }],
+                        },
+                    },
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_unexpected_token_carries_span() {
+        let mut parser = Parser::new("let = 5;").unwrap();
+        let err = parser.parse_program().unwrap_err();
+        assert_eq!(err.span().column, 5);
+    }
+}